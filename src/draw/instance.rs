@@ -0,0 +1,91 @@
+//! A transform applied to a single copy of a primitive when drawing many copies of it at once.
+//!
+//! See `Quad::instances`, which bakes each `Instance`'s transform directly into tessellated
+//! geometry on the CPU via `transform_point2` rather than uploading a per-instance GPU buffer -
+//! cheap enough for the particle/grid-sized instance counts sketches actually draw, and it avoids
+//! a second vertex buffer and pipeline layout per primitive type.
+
+use crate::draw::properties::LinSrgba;
+use crate::geom::{Point2, Vector3};
+use crate::math::BaseFloat;
+
+/// A single instance's translation, rotation, scale and optional color override.
+#[derive(Clone, Copy, Debug)]
+pub struct Instance<S = crate::geom::scalar::Default> {
+    pub translation: Vector3<S>,
+    /// Rotation around the z axis, in radians.
+    pub rotation: S,
+    pub scale: Vector3<S>,
+    /// Overrides the primitive's own resolved color for this instance, if present.
+    pub color: Option<LinSrgba>,
+}
+
+impl<S> Instance<S>
+where
+    S: BaseFloat,
+{
+    /// An instance with no translation, rotation or scaling applied, and no color override.
+    pub fn identity() -> Self {
+        Instance {
+            translation: Vector3 {
+                x: S::zero(),
+                y: S::zero(),
+                z: S::zero(),
+            },
+            rotation: S::zero(),
+            scale: Vector3 {
+                x: S::one(),
+                y: S::one(),
+                z: S::one(),
+            },
+            color: None,
+        }
+    }
+
+    /// Apply this instance's translation, z-rotation and scale to a point in the primitive's
+    /// local 2D space.
+    ///
+    /// Used by `Quad::instances` to bake each instance directly into tessellated geometry on the
+    /// CPU.
+    pub(crate) fn transform_point2(&self, p: Point2<S>) -> Point2<S> {
+        let f = |s: S| s.to_f32().unwrap_or(0.0);
+        let (px, py) = (f(p.x), f(p.y));
+        let (sx, sy) = (f(self.scale.x), f(self.scale.y));
+        let (tx, ty) = (f(self.translation.x), f(self.translation.y));
+        let r = f(self.rotation);
+        let (cos_r, sin_r) = (r.cos(), r.sin());
+        let (scaled_x, scaled_y) = (px * sx, py * sy);
+        let x = scaled_x * cos_r - scaled_y * sin_r + tx;
+        let y = scaled_x * sin_r + scaled_y * cos_r + ty;
+        Point2 {
+            x: S::from(x).unwrap_or_else(S::zero),
+            y: S::from(y).unwrap_or_else(S::zero),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transform_point2_composes_scale_rotation_and_translation() {
+        let instance = Instance {
+            translation: Vector3 {
+                x: 10.0,
+                y: 20.0,
+                z: 0.0,
+            },
+            rotation: std::f32::consts::FRAC_PI_2,
+            scale: Vector3 {
+                x: 2.0,
+                y: 2.0,
+                z: 1.0,
+            },
+            color: None,
+        };
+        let p = instance.transform_point2(Point2 { x: 1.0, y: 0.0 });
+        assert!((p.x - 10.0).abs() < 1e-5);
+        assert!((p.y - 22.0).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,59 @@
+//! Items related to the intermediary mesh used to efficiently store **Draw** vertex and index
+//! data before it is transformed and inserted into the **Draw**'s inner **Mesh**.
+
+pub mod vertex;
+
+pub use self::vertex::Vertex;
+
+use std::ops;
+
+/// The raw vertex data stored within the intermediary mesh, indexed into by ranges when
+/// producing a primitive's vertices.
+#[derive(Clone, Debug)]
+pub struct IntermediaryVertexData<S = crate::geom::scalar::Default> {
+    pub points: Vec<vertex::Point<S>>,
+    pub colors: Vec<vertex::Color>,
+    pub tex_coords: Vec<vertex::TexCoords<S>>,
+    pub normals: Vec<vertex::Normal<S>>,
+}
+
+/// The ranges into the `IntermediaryVertexData` used by a single primitive's vertices.
+///
+/// Only `points` is guaranteed to be non-empty - `colors`, `tex_coords` and `normals` fall back
+/// to the current fill color, `vertex::default_tex_coords` and `vertex::default_normal`
+/// respectively when their range is empty.
+#[derive(Clone, Debug, Default)]
+pub struct IntermediaryVertexDataRanges {
+    pub points: ops::Range<usize>,
+    pub colors: ops::Range<usize>,
+    pub tex_coords: ops::Range<usize>,
+    pub normals: ops::Range<usize>,
+}
+
+/// The intermediary mesh used to store vertex and index data before it is submitted to the
+/// **Draw**'s inner **Mesh**.
+#[derive(Clone, Debug)]
+pub struct IntermediaryMesh<S = crate::geom::scalar::Default> {
+    pub vertex_data: IntermediaryVertexData<S>,
+    pub indices: Vec<usize>,
+}
+
+impl<S> Default for IntermediaryVertexData<S> {
+    fn default() -> Self {
+        IntermediaryVertexData {
+            points: Vec::new(),
+            colors: Vec::new(),
+            tex_coords: Vec::new(),
+            normals: Vec::new(),
+        }
+    }
+}
+
+impl<S> Default for IntermediaryMesh<S> {
+    fn default() -> Self {
+        IntermediaryMesh {
+            vertex_data: Default::default(),
+            indices: Vec::new(),
+        }
+    }
+}
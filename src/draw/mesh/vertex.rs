@@ -0,0 +1,70 @@
+//! The vertex type used to describe geometry within the intermediary mesh.
+
+use crate::draw::properties::color::DefaultLinSrgba;
+use crate::geom::{Point2, Point3, Vector3};
+use crate::math::BaseFloat;
+
+/// The point type used to describe a **Vertex**'s location.
+pub type Point<S> = Point3<S>;
+
+/// The color type used to describe a **Vertex**'s color.
+pub type Color = DefaultLinSrgba;
+
+/// The texture coordinate type used to describe a **Vertex**'s position within a texture.
+pub type TexCoords<S> = Point2<S>;
+
+/// The normal type used to describe a **Vertex**'s surface normal.
+pub type Normal<S> = Vector3<S>;
+
+/// A vertex of the intermediary mesh, ready to be transformed and inserted into the **Draw**'s
+/// inner **Mesh**.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Vertex<S = crate::geom::scalar::Default> {
+    /// The location of the vertex in 3D space.
+    pub point: Point<S>,
+    /// The color of the vertex.
+    pub color: Color,
+    /// The coordinates of the vertex within a texture.
+    ///
+    /// Uses the top-left of the texture as the origin.
+    pub tex_coords: TexCoords<S>,
+    /// The normal of the surface at the vertex, used for lighting calculations.
+    pub normal: Normal<S>,
+}
+
+/// The default texture coordinates used for vertices that do not specify their own.
+pub fn default_tex_coords<S>() -> TexCoords<S>
+where
+    S: BaseFloat,
+{
+    Point2 {
+        x: S::zero(),
+        y: S::zero(),
+    }
+}
+
+/// The default surface normal used for vertices that do not specify their own.
+///
+/// Points directly out of the screen along the positive `z` axis, matching the assumption made by
+/// flat 2D drawing.
+pub fn default_normal<S>() -> Normal<S>
+where
+    S: BaseFloat,
+{
+    Vector3 {
+        x: S::zero(),
+        y: S::zero(),
+        z: S::one(),
+    }
+}
+
+/// Construct a new **Vertex**.
+pub fn new<S>(point: Point<S>, color: Color, tex_coords: TexCoords<S>, normal: Normal<S>) -> Vertex<S> {
+    Vertex {
+        point,
+        color,
+        tex_coords,
+        normal,
+    }
+}
@@ -0,0 +1,310 @@
+//! Gradient fills for polygon-based **Draw** primitives.
+//!
+//! A **Gradient** projects a point in local, pre-transform primitive space through some
+//! **Geometry** (a line for `Linear`, a point and radius for `Radial`) onto a raw `0.0..=1.0`
+//! coordinate, extends that coordinate past its range according to a **Spread** mode, then looks
+//! up the resulting color with a piecewise-linear blend between the two nearest **Stop**s.
+
+use crate::draw::properties::LinSrgba;
+use crate::geom::Point2;
+use crate::math::BaseFloat;
+
+/// A single color stop within a **Gradient**.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stop {
+    /// Where the stop sits along the gradient, normalized within `0.0..=1.0`.
+    pub ratio: f32,
+    /// The stop's color.
+    pub color: LinSrgba,
+}
+
+impl Stop {
+    /// Shorthand for constructing a **Stop**.
+    pub fn new(ratio: f32, color: LinSrgba) -> Self {
+        Stop { ratio, color }
+    }
+}
+
+/// The geometry used to project a point in local (pre-transform) primitive space onto a
+/// gradient's raw, un-spread coordinate.
+#[derive(Clone, Copy, Debug)]
+pub enum Geometry<S> {
+    /// Projects onto the line from `start` to `end` - `start` maps to `0.0`, `end` to `1.0`.
+    Linear { start: Point2<S>, end: Point2<S> },
+    /// Projects onto the distance from `center`, normalized by `radius`.
+    Radial { center: Point2<S>, radius: S },
+}
+
+/// The kind of **Gradient**, as selected by the `linear_gradient`/`radial_gradient` constructors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GradientType {
+    Linear,
+    Radial,
+}
+
+/// How a gradient's coordinate is extended beyond its `0.0..=1.0` range.
+///
+/// Only `Pad` can currently be passed to `Gradient::spread` - see its doc comment for why.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Spread {
+    /// Clamp to the nearest edge stop - the gradient appears to hold its end colors.
+    Pad,
+    /// Mirror back and forth past each end, so the gradient appears to bounce.
+    Reflect,
+    /// Wrap back to the start past the end, so the gradient repeats.
+    Repeat,
+}
+
+/// The color space within which to interpolate between two neighbouring stops.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorSpace {
+    /// Interpolate within linear RGB.
+    LinearRgb,
+    /// Interpolate within gamma-corrected sRGB, matching how many vector-graphics tools blend
+    /// gradients.
+    Srgb,
+}
+
+/// A gradient fill for a polygon-based primitive.
+///
+/// Construct via `Gradient::linear` or `Gradient::radial`, both of which take `start`/`end` or
+/// `center`/`radius` in the same local, pre-transform space as the primitive's own points (e.g.
+/// the `points` passed to `Quad::points`).
+#[derive(Clone, Debug)]
+pub struct Gradient<S = crate::geom::scalar::Default> {
+    pub geometry: Geometry<S>,
+    pub stops: Vec<Stop>,
+    pub spread: Spread,
+    pub color_space: ColorSpace,
+}
+
+impl<S> Gradient<S> {
+    /// Begin a linear gradient running from `start` to `end`.
+    pub fn linear(start: Point2<S>, end: Point2<S>, stops: Vec<Stop>) -> Self {
+        Gradient {
+            geometry: Geometry::Linear { start, end },
+            stops,
+            spread: Spread::Pad,
+            color_space: ColorSpace::LinearRgb,
+        }
+    }
+
+    /// Begin a radial gradient centred at `center` with the given `radius`.
+    pub fn radial(center: Point2<S>, radius: S, stops: Vec<Stop>) -> Self {
+        Gradient {
+            geometry: Geometry::Radial { center, radius },
+            stops,
+            spread: Spread::Pad,
+            color_space: ColorSpace::LinearRgb,
+        }
+    }
+
+    /// The `GradientType` of this gradient's `Geometry`.
+    pub fn ty(&self) -> GradientType {
+        match self.geometry {
+            Geometry::Linear { .. } => GradientType::Linear,
+            Geometry::Radial { .. } => GradientType::Radial,
+        }
+    }
+
+    /// Specify the spread mode used to extend the gradient past its `0.0..=1.0` range.
+    ///
+    /// Only `Spread::Pad` is supported for now - `Gradient::sample` is evaluated once per
+    /// tessellated vertex and left for the GPU rasterizer to linearly interpolate across each
+    /// triangle, which is only correct for `Pad`. `Repeat`/`Reflect` are periodic functions of
+    /// position, so a period boundary falling inside a triangle (rather than exactly on a vertex)
+    /// would linearly interpolate straight across the wrap/bounce instead of repeating or
+    /// reflecting, producing a visible seam. Panics if given `Spread::Repeat` or
+    /// `Spread::Reflect`.
+    pub fn spread(mut self, spread: Spread) -> Self {
+        assert_eq!(
+            spread,
+            Spread::Pad,
+            "`Spread::Repeat`/`Spread::Reflect` aren't supported yet - `Gradient` is only \
+             sampled once per tessellated vertex, which can't correctly render a spread mode \
+             that's periodic in position without a per-fragment implementation",
+        );
+        self.spread = spread;
+        self
+    }
+
+    /// Specify the color space within which to interpolate between stops.
+    pub fn color_space(mut self, color_space: ColorSpace) -> Self {
+        self.color_space = color_space;
+        self
+    }
+}
+
+impl<S> Gradient<S>
+where
+    S: BaseFloat,
+{
+    /// Sample the color of this gradient at `point`, a point in the same local, pre-transform
+    /// space as the `Geometry`'s `start`/`end` or `center`.
+    ///
+    /// Projects `point` onto the gradient's raw coordinate, extends it past `0.0..=1.0` via
+    /// `spread`, then looks up the resulting coordinate with a piecewise-linear blend between the
+    /// two nearest stops.
+    pub fn sample(&self, point: Point2<S>) -> LinSrgba {
+        let raw_t = self.project(point);
+        let t = apply_spread(self.spread, raw_t);
+        sample_stops(&self.stops, self.color_space, t)
+    }
+
+    // Project `point` onto the gradient's raw (un-spread) `0.0..=1.0` coordinate.
+    fn project(&self, point: Point2<S>) -> f32 {
+        let t = match self.geometry {
+            Geometry::Linear { start, end } => {
+                let axis = end - start;
+                let len2 = axis.x * axis.x + axis.y * axis.y;
+                if len2 <= S::zero() {
+                    S::zero()
+                } else {
+                    let rel = point - start;
+                    (rel.x * axis.x + rel.y * axis.y) / len2
+                }
+            }
+            Geometry::Radial { center, radius } => {
+                if radius <= S::zero() {
+                    S::zero()
+                } else {
+                    let rel = point - center;
+                    (rel.x * rel.x + rel.y * rel.y).sqrt() / radius
+                }
+            }
+        };
+        t.to_f32().unwrap_or(0.0)
+    }
+}
+
+// Map a raw gradient coordinate through the given spread mode.
+fn apply_spread(spread: Spread, t: f32) -> f32 {
+    match spread {
+        Spread::Pad => t.max(0.0).min(1.0),
+        Spread::Repeat => t.rem_euclid(1.0),
+        Spread::Reflect => {
+            let t = t.rem_euclid(2.0);
+            if t > 1.0 {
+                2.0 - t
+            } else {
+                t
+            }
+        }
+    }
+}
+
+// Piecewise-linear lookup of the color at `t` (expected to already be spread into `0.0..=1.0`)
+// across an ordered list of stops.
+//
+// Falls back to fully transparent black for an empty stop list and clamps to the nearest end
+// stop's color beyond the first/last ratio.
+fn sample_stops(stops: &[Stop], color_space: ColorSpace, t: f32) -> LinSrgba {
+    let first = match stops.first() {
+        None => return LinSrgba::new(0.0, 0.0, 0.0, 0.0),
+        Some(stop) => stop,
+    };
+    let last = stops.last().expect("checked non-empty above");
+    if stops.len() == 1 || t <= first.ratio {
+        return first.color;
+    }
+    if t >= last.ratio {
+        return last.color;
+    }
+    for pair in stops.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        if t >= a.ratio && t <= b.ratio {
+            let span = b.ratio - a.ratio;
+            let local_t = if span <= std::f32::EPSILON {
+                0.0
+            } else {
+                (t - a.ratio) / span
+            };
+            return mix(a.color, b.color, local_t, color_space);
+        }
+    }
+    last.color
+}
+
+// Linearly interpolate between two colors, either directly in linear-RGB or by gamma-correcting
+// through sRGB first, matching the authoring-friendly blend used by vector-graphics tools.
+fn mix(a: LinSrgba, b: LinSrgba, t: f32, color_space: ColorSpace) -> LinSrgba {
+    match color_space {
+        ColorSpace::LinearRgb => LinSrgba::new(
+            lerp(a.red, b.red, t),
+            lerp(a.green, b.green, t),
+            lerp(a.blue, b.blue, t),
+            lerp(a.alpha, b.alpha, t),
+        ),
+        ColorSpace::Srgb => {
+            const GAMMA: f32 = 2.2;
+            let to_srgb = |c: f32| c.max(0.0).powf(1.0 / GAMMA);
+            let to_linear = |c: f32| c.max(0.0).powf(GAMMA);
+            LinSrgba::new(
+                to_linear(lerp(to_srgb(a.red), to_srgb(b.red), t)),
+                to_linear(lerp(to_srgb(a.green), to_srgb(b.green), t)),
+                to_linear(lerp(to_srgb(a.blue), to_srgb(b.blue), t)),
+                lerp(a.alpha, b.alpha, t),
+            )
+        }
+    }
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_spread_pad_clamps_to_0_1() {
+        assert_eq!(apply_spread(Spread::Pad, -0.5), 0.0);
+        assert_eq!(apply_spread(Spread::Pad, 0.5), 0.5);
+        assert_eq!(apply_spread(Spread::Pad, 1.5), 1.0);
+    }
+
+    #[test]
+    fn apply_spread_repeat_wraps() {
+        assert_eq!(apply_spread(Spread::Repeat, 1.25), 0.25);
+        assert_eq!(apply_spread(Spread::Repeat, -0.25), 0.75);
+    }
+
+    #[test]
+    fn apply_spread_reflect_bounces() {
+        assert_eq!(apply_spread(Spread::Reflect, 0.5), 0.5);
+        assert_eq!(apply_spread(Spread::Reflect, 1.25), 0.75);
+        assert_eq!(apply_spread(Spread::Reflect, 2.0), 0.0);
+    }
+
+    #[test]
+    fn sample_stops_empty_is_transparent_black() {
+        let color = sample_stops(&[], ColorSpace::LinearRgb, 0.5);
+        assert_eq!(color, LinSrgba::new(0.0, 0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn sample_stops_clamps_beyond_first_and_last() {
+        let red = LinSrgba::new(1.0, 0.0, 0.0, 1.0);
+        let blue = LinSrgba::new(0.0, 0.0, 1.0, 1.0);
+        let stops = vec![Stop::new(0.25, red), Stop::new(0.75, blue)];
+        assert_eq!(sample_stops(&stops, ColorSpace::LinearRgb, 0.0), red);
+        assert_eq!(sample_stops(&stops, ColorSpace::LinearRgb, 1.0), blue);
+    }
+
+    #[test]
+    fn sample_stops_interpolates_between_nearest_pair() {
+        let red = LinSrgba::new(1.0, 0.0, 0.0, 1.0);
+        let blue = LinSrgba::new(0.0, 0.0, 1.0, 1.0);
+        let stops = vec![Stop::new(0.0, red), Stop::new(1.0, blue)];
+        let mid = sample_stops(&stops, ColorSpace::LinearRgb, 0.5);
+        assert_eq!(mid, LinSrgba::new(0.5, 0.0, 0.5, 1.0));
+    }
+
+    #[test]
+    fn mix_linear_rgb_is_a_plain_lerp() {
+        let a = LinSrgba::new(0.0, 0.0, 0.0, 0.0);
+        let b = LinSrgba::new(1.0, 1.0, 1.0, 1.0);
+        assert_eq!(mix(a, b, 0.25, ColorSpace::LinearRgb), LinSrgba::new(0.25, 0.25, 0.25, 0.25));
+    }
+}
@@ -0,0 +1,317 @@
+//! Items shared by the polygon-based primitives (`Quad`, `Rect`, `Ellipse` and `Polygon` itself):
+//! the common `PolygonOptions`, the `SetPolygon` builder trait, and the tessellation that turns a
+//! primitive's points into mesh vertices and indices.
+
+pub mod gradient;
+
+pub use self::gradient::{ColorSpace, Gradient, GradientType, Spread, Stop};
+
+use crate::draw::mesh::vertex::Point as VertexPoint;
+use crate::draw::properties::spatial::{self, orientation, position};
+use crate::draw::properties::{
+    ColorScalar, ColorTransform, Draw, Drawn, DrawingContext, IndicesFromRange, LinSrgba,
+    SetColor, SetColorTransform, SetOrientation, SetPosition, SetStroke, VerticesFromRanges,
+};
+use crate::draw::{self, theme};
+use crate::geom::{self, Point2};
+use crate::math::BaseFloat;
+use lyon::tessellation::StrokeOptions;
+use std::ops;
+
+/// The set of options shared by all polygon-based primitives.
+#[derive(Clone, Debug, Default)]
+pub struct PolygonOptions<S = geom::scalar::Default> {
+    pub position: position::Properties<S>,
+    pub orientation: orientation::Properties<S>,
+    pub no_fill: bool,
+    pub fill_color: Option<LinSrgba>,
+    pub fill_gradient: Option<Gradient<S>>,
+    pub stroke: Option<StrokeOptions>,
+    pub color_transform: ColorTransform,
+}
+
+/// The beginning of the polygon-building process, before the points or vertices are specified.
+#[derive(Clone, Debug, Default)]
+pub struct PolygonInit<S = geom::scalar::Default>(pub PolygonOptions<S>);
+
+/// A polygon-based primitive whose points have been tessellated into the intermediary mesh and
+/// which is ready to resolve its final fill color (a flat color, a gradient, or the theme's
+/// default for the primitive) once drawn.
+#[derive(Clone, Debug)]
+pub struct Polygon<S = geom::scalar::Default> {
+    opts: PolygonOptions<S>,
+    points: Vec<Point2<S>>,
+    /// The extra pivot vertex pushed ahead of `points` when `points` tessellated a gradient fill
+    /// as a centroid fan rather than a perimeter fan (see `PolygonInit::points`).
+    centroid: Option<Point2<S>>,
+    point_range: ops::Range<usize>,
+    index_range: ops::Range<usize>,
+}
+
+/// The **Vertices** iterator yielded by a tessellated polygon-based primitive.
+pub type PolygonVertices = VerticesFromRanges;
+
+/// The **Indices** iterator yielded by a tessellated polygon-based primitive.
+pub type PolygonIndices = IndicesFromRange;
+
+/// Types that provide access to a `PolygonOptions` may use the `SetPolygon` builder methods.
+pub trait SetPolygon<S = geom::scalar::Default>: Sized {
+    /// Provide mutable access to the polygon options.
+    fn polygon_options_mut(&mut self) -> &mut PolygonOptions<S>;
+
+    /// Don't fill the polygon with any color or gradient.
+    ///
+    /// The stroke options are not tessellated by `Polygon::into_drawn_themed`, so no stroke is
+    /// drawn in place of the fill.
+    fn no_fill(mut self) -> Self {
+        self.polygon_options_mut().no_fill = true;
+        self
+    }
+
+    /// Fill the polygon with a linear gradient running from `start` to `end` (in the same local,
+    /// pre-transform space as the primitive's own points), blending through `stops` in order.
+    ///
+    /// Overrides any flat fill color previously set via `SetColor`.
+    fn linear_gradient(mut self, start: Point2<S>, end: Point2<S>, stops: Vec<Stop>) -> Self {
+        self.polygon_options_mut().fill_gradient = Some(Gradient::linear(start, end, stops));
+        self
+    }
+
+    /// Fill the polygon with a radial gradient centred at `center` with the given `radius` (in
+    /// the same local, pre-transform space as the primitive's own points), blending through
+    /// `stops` in order.
+    ///
+    /// Overrides any flat fill color previously set via `SetColor`.
+    fn radial_gradient(mut self, center: Point2<S>, radius: S, stops: Vec<Stop>) -> Self {
+        self.polygon_options_mut().fill_gradient = Some(Gradient::radial(center, radius, stops));
+        self
+    }
+}
+
+impl<S> PolygonInit<S>
+where
+    S: BaseFloat,
+{
+    /// Triangulate the given (assumed convex, e.g. a `Quad`, `Rect` or `Ellipse`) `points`, insert
+    /// them into the intermediary mesh, and produce a `Polygon` ready to resolve its final fill
+    /// color.
+    ///
+    /// When a gradient fill has already been set (via `linear_gradient`/`radial_gradient`), the
+    /// points are fanned out from their centroid rather than from `points[0]`, so every triangle
+    /// shares the same central pivot instead of an arbitrary perimeter vertex - see the comment on
+    /// the gradient color pass in `into_drawn_themed` for why this matters for `radial_gradient`.
+    /// Otherwise a plain perimeter fan is used, as tessellating a flat color doesn't care which
+    /// vertex the fan pivots on.
+    pub fn points<I>(self, mut ctxt: DrawingContext<S>, points: I) -> Polygon<S>
+    where
+        I: IntoIterator<Item = Point2<S>>,
+    {
+        let PolygonInit(opts) = self;
+        let points: Vec<Point2<S>> = points.into_iter().collect();
+        let centroid = match opts.fill_gradient {
+            Some(_) if points.len() >= 3 => Some(centroid_of(&points)),
+            _ => None,
+        };
+
+        let start_point_ix = ctxt.mesh.vertex_data.points.len();
+        if let Some(centroid) = centroid {
+            ctxt.mesh.vertex_data.points.push(VertexPoint {
+                x: centroid.x,
+                y: centroid.y,
+                z: S::zero(),
+            });
+        }
+        for &p in &points {
+            ctxt.mesh.vertex_data.points.push(VertexPoint {
+                x: p.x,
+                y: p.y,
+                z: S::zero(),
+            });
+        }
+        let point_range = start_point_ix..ctxt.mesh.vertex_data.points.len();
+
+        let start_index_ix = ctxt.mesh.indices.len();
+        let n = points.len();
+        match centroid {
+            Some(_) => {
+                let centroid_ix = start_point_ix;
+                let first_ix = start_point_ix + 1;
+                for i in 0..n {
+                    ctxt.mesh.indices.push(centroid_ix);
+                    ctxt.mesh.indices.push(first_ix + i);
+                    ctxt.mesh.indices.push(first_ix + (i + 1) % n);
+                }
+            }
+            None if n >= 3 => {
+                for i in 1..(n - 1) {
+                    ctxt.mesh.indices.push(start_point_ix);
+                    ctxt.mesh.indices.push(start_point_ix + i);
+                    ctxt.mesh.indices.push(start_point_ix + i + 1);
+                }
+            }
+            None => (),
+        }
+        let index_range = start_index_ix..ctxt.mesh.indices.len();
+
+        Polygon {
+            opts,
+            points,
+            centroid,
+            point_range,
+            index_range,
+        }
+    }
+}
+
+// The unweighted average of `points`, used to pivot a gradient fill's fan at the shape's center
+// rather than at an arbitrary perimeter point.
+fn centroid_of<S>(points: &[Point2<S>]) -> Point2<S>
+where
+    S: BaseFloat,
+{
+    let mut sum_x = S::zero();
+    let mut sum_y = S::zero();
+    for p in points {
+        sum_x = sum_x + p.x;
+        sum_y = sum_y + p.y;
+    }
+    let n = S::from(points.len()).expect("point count did not fit in `S`");
+    Point2 {
+        x: sum_x / n,
+        y: sum_y / n,
+    }
+}
+
+impl<S> Polygon<S>
+where
+    S: BaseFloat,
+{
+    /// Resolve the polygon's fill (a gradient, a flat color, or the given theme primitive's
+    /// default color when neither was set) and produce the primitive's final `Drawn` vertices and
+    /// indices.
+    ///
+    /// Only the fill is drawn: any stroke options set via `SetStroke` are ignored.
+    pub fn into_drawn_themed(
+        self,
+        draw: Draw<S>,
+        theme_primitive: &theme::Primitive,
+    ) -> Drawn<S, PolygonVertices, PolygonIndices> {
+        let Polygon {
+            opts,
+            points,
+            centroid,
+            point_range,
+            index_range,
+        } = self;
+        let PolygonOptions {
+            position,
+            orientation,
+            no_fill,
+            fill_color,
+            fill_gradient,
+            stroke: _,
+            color_transform,
+        } = opts;
+
+        let (color_range, fill_color) = match (no_fill, fill_gradient) {
+            (true, _) => (0..0, None),
+            (false, Some(gradient)) => {
+                // A gradient is evaluated per-vertex here (not per-fragment) and relies on the
+                // GPU's rasterizer to linearly interpolate the resulting colors across each
+                // triangle, so a stop or `Spread` boundary that falls inside a triangle rather
+                // than at a vertex is blended across instead of being reproduced exactly (see
+                // `Gradient::spread`). `PolygonInit::points` at least pivots the fan at
+                // the shape's centroid in this case (rather than an arbitrary perimeter point) so
+                // a `radial_gradient`'s roughly-circular falloff isn't also faceted by the choice
+                // of fan pivot; pushing that centroid's own color here first keeps it in the same
+                // order `PolygonInit::points` pushed the matching point.
+                let color_range = draw.drawing_context(|mut ctxt| {
+                    let start = ctxt.mesh.vertex_data.colors.len();
+                    if let Some(centroid) = centroid {
+                        let color = color_transform.apply(gradient.sample(centroid));
+                        ctxt.mesh.vertex_data.colors.push(color);
+                    }
+                    for &p in &points {
+                        let color = color_transform.apply(gradient.sample(p));
+                        ctxt.mesh.vertex_data.colors.push(color);
+                    }
+                    start..ctxt.mesh.vertex_data.colors.len()
+                });
+                (color_range, None)
+            }
+            (false, None) => {
+                let color = fill_color.unwrap_or_else(|| draw.theme().fill_lin_srgba(theme_primitive));
+                (0..0, Some(color_transform.apply(color)))
+            }
+        };
+
+        let vertex_ranges = draw::IntermediaryVertexDataRanges {
+            points: point_range,
+            colors: color_range,
+            tex_coords: 0..0,
+            normals: 0..0,
+        };
+        let vertices = VerticesFromRanges::new(vertex_ranges, fill_color);
+        let indices = IndicesFromRange::new(index_range, 0);
+        let properties = spatial::Properties {
+            position,
+            orientation,
+        };
+        (properties, vertices, indices)
+    }
+}
+
+impl<S> SetOrientation<S> for PolygonInit<S> {
+    fn properties(&mut self) -> &mut orientation::Properties<S> {
+        &mut self.0.orientation
+    }
+}
+
+impl<S> SetPosition<S> for PolygonInit<S> {
+    fn properties(&mut self) -> &mut position::Properties<S> {
+        &mut self.0.position
+    }
+}
+
+impl<S> SetColor<ColorScalar> for PolygonInit<S> {
+    fn rgba_mut(&mut self) -> &mut Option<LinSrgba> {
+        &mut self.0.fill_color
+    }
+}
+
+impl<S> SetStroke for PolygonInit<S> {
+    fn stroke_options_mut(&mut self) -> &mut StrokeOptions {
+        self.0
+            .stroke
+            .get_or_insert_with(StrokeOptions::default)
+    }
+}
+
+impl<S> SetPolygon<S> for PolygonInit<S> {
+    fn polygon_options_mut(&mut self) -> &mut PolygonOptions<S> {
+        &mut self.0
+    }
+}
+
+impl<S> SetColorTransform for PolygonInit<S> {
+    fn color_transform_mut(&mut self) -> &mut ColorTransform {
+        &mut self.0.color_transform
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn centroid_of_square_is_its_middle() {
+        let points = vec![
+            Point2 { x: 0.0, y: 0.0 },
+            Point2 { x: 2.0, y: 0.0 },
+            Point2 { x: 2.0, y: 2.0 },
+            Point2 { x: 0.0, y: 2.0 },
+        ];
+        let c: Point2<f64> = centroid_of(&points);
+        assert_eq!(c, Point2 { x: 1.0, y: 1.0 });
+    }
+}
@@ -1,14 +1,16 @@
 use crate::color::conv::IntoLinSrgba;
+use crate::draw::instance::Instance;
+use crate::draw::mesh::vertex::Point as VertexPoint;
 use crate::draw::primitive::polygon::{
     PolygonIndices, PolygonInit, PolygonOptions, PolygonVertices, SetPolygon,
 };
 use crate::draw::primitive::Primitive;
 use crate::draw::properties::spatial::{dimension, orientation, position};
 use crate::draw::properties::{
-    spatial, ColorScalar, Draw, Drawn, IntoDrawn, LinSrgba, SetColor, SetDimensions,
-    SetOrientation, SetPosition, SetStroke,
+    spatial, ColorScalar, ColorTransform, Draw, Drawn, IntoDrawn, LinSrgba, SetColor,
+    SetColorTransform, SetDimensions, SetOrientation, SetPosition, SetStroke,
 };
-use crate::draw::{theme, Drawing};
+use crate::draw::{self, theme, Drawing};
 use crate::geom::{self, Point2, Vector2};
 use crate::math::{BaseFloat, ElementWise};
 use lyon::tessellation::StrokeOptions;
@@ -19,6 +21,10 @@ pub struct Quad<S = geom::scalar::Default> {
     quad: geom::Quad<Point2<S>>,
     polygon: PolygonInit<S>,
     dimensions: spatial::dimension::Properties<S>,
+    /// When non-empty, the quad is drawn as `instances.len()` transformed copies baked into its
+    /// own geometry on the CPU (see `draw::instance`) rather than via the usual single-primitive
+    /// path.
+    instances: Vec<Instance<S>>,
 }
 
 /// The drawing context for a `Quad`.
@@ -47,6 +53,22 @@ impl<S> Quad<S> {
         self.quad = geom::Quad([a, b, c, d]);
         self
     }
+
+    /// Render `instances.len()` copies of this quad, each positioned by its own translation,
+    /// rotation and scale, instead of the usual single-quad path.
+    ///
+    /// Every copy is transformed on the CPU and written into this one primitive's vertices and
+    /// indices; no per-instance GPU buffer or instanced draw call is used.
+    ///
+    /// Useful for sketches drawing thousands of identical shapes (particles, grids) where
+    /// per-shape draw-call overhead would otherwise dominate.
+    pub fn instances(mut self, instances: &[Instance<S>]) -> Self
+    where
+        S: Clone,
+    {
+        self.instances = instances.to_vec();
+        self
+    }
 }
 
 // Trait implementations.
@@ -62,6 +84,7 @@ where
             mut quad,
             polygon,
             dimensions,
+            instances,
         } = self;
 
         // If dimensions were specified, scale the points to those dimensions.
@@ -84,13 +107,98 @@ where
             quad = geom::Quad([new_a, new_b, new_c, new_d]);
         }
 
-        // The color.
-        let points = quad.vertices();
-        let polygon = draw.drawing_context(|ctxt| polygon.points(ctxt, points));
-        polygon.into_drawn_themed(draw, &theme::Primitive::Quad)
+        let points: Vec<Point2<S>> = quad.vertices().into_iter().collect();
+
+        if instances.is_empty() {
+            let polygon = draw.drawing_context(|ctxt| polygon.points(ctxt, points));
+            return polygon.into_drawn_themed(draw, &theme::Primitive::Quad);
+        }
+        into_drawn_instanced(draw, polygon, &points, &instances)
     }
 }
 
+// Bake each `Instance`'s translation, rotation and scale into its own transformed, colored copy of
+// `base_points`, writing every copy into one contiguous point/color/index range so an instanced
+// quad still yields a single `VerticesFromRanges`/`IndicesFromRange` pair, just like the
+// non-instanced path above.
+//
+// This runs the per-instance transform on the CPU rather than via a per-instance GPU buffer - see
+// the note on `draw::instance` for why. A gradient fill isn't supported in
+// combination with `instances` (each instance falls back to a flat color, overridden per-instance
+// by `Instance::color`) since there is no single local space left to project a `Gradient` through
+// once every instance has its own transform.
+fn into_drawn_instanced<S>(
+    mut draw: Draw<S>,
+    polygon: PolygonInit<S>,
+    base_points: &[Point2<S>],
+    instances: &[Instance<S>],
+) -> Drawn<S, PolygonVertices, PolygonIndices>
+where
+    S: BaseFloat,
+{
+    let PolygonInit(opts) = polygon;
+    let PolygonOptions {
+        position,
+        orientation,
+        no_fill: _,
+        fill_color,
+        fill_gradient: _,
+        stroke: _,
+        color_transform,
+    } = opts;
+    let theme_color = draw.theme().fill_lin_srgba(&theme::Primitive::Quad);
+    let default_color = color_transform.apply(fill_color.unwrap_or(theme_color));
+
+    let n = base_points.len();
+    let (point_range, color_range, index_range) = draw.drawing_context(|mut ctxt| {
+        let start_point_ix = ctxt.mesh.vertex_data.points.len();
+        let start_color_ix = ctxt.mesh.vertex_data.colors.len();
+        let start_index_ix = ctxt.mesh.indices.len();
+        for instance in instances {
+            let base_ix = ctxt.mesh.vertex_data.points.len();
+            let color = instance
+                .color
+                .map(|c| color_transform.apply(c))
+                .unwrap_or(default_color);
+            for &p in base_points {
+                let p = instance.transform_point2(p);
+                ctxt.mesh.vertex_data.points.push(VertexPoint {
+                    x: p.x,
+                    y: p.y,
+                    z: S::zero(),
+                });
+                ctxt.mesh.vertex_data.colors.push(color);
+            }
+            if n >= 3 {
+                for i in 1..(n - 1) {
+                    ctxt.mesh.indices.push(base_ix);
+                    ctxt.mesh.indices.push(base_ix + i);
+                    ctxt.mesh.indices.push(base_ix + i + 1);
+                }
+            }
+        }
+        (
+            start_point_ix..ctxt.mesh.vertex_data.points.len(),
+            start_color_ix..ctxt.mesh.vertex_data.colors.len(),
+            start_index_ix..ctxt.mesh.indices.len(),
+        )
+    });
+
+    let vertex_ranges = draw::IntermediaryVertexDataRanges {
+        points: point_range,
+        colors: color_range,
+        tex_coords: 0..0,
+        normals: 0..0,
+    };
+    let vertices = PolygonVertices::new(vertex_ranges, None);
+    let indices = PolygonIndices::new(index_range, 0);
+    let properties = spatial::Properties {
+        position,
+        orientation,
+    };
+    (properties, vertices, indices)
+}
+
 impl<S> From<geom::Quad<Point2<S>>> for Quad<S>
 where
     S: BaseFloat,
@@ -98,10 +206,12 @@ where
     fn from(quad: geom::Quad<Point2<S>>) -> Self {
         let polygon = Default::default();
         let dimensions = Default::default();
+        let instances = Vec::new();
         Quad {
             polygon,
             dimensions,
             quad,
+            instances,
         }
     }
 }
@@ -152,6 +262,12 @@ impl<S> SetColor<ColorScalar> for Quad<S> {
     }
 }
 
+impl<S> SetColorTransform for Quad<S> {
+    fn color_transform_mut(&mut self) -> &mut ColorTransform {
+        SetColorTransform::color_transform_mut(&mut self.polygon)
+    }
+}
+
 impl<S> SetStroke for Quad<S> {
     fn stroke_options_mut(&mut self) -> &mut StrokeOptions {
         SetStroke::stroke_options_mut(&mut self.polygon)
@@ -194,4 +310,18 @@ where
     {
         self.map_ty(|ty| ty.points(a, b, c, d))
     }
+
+    /// Render `instances.len()` transformed copies of this quad. See `Quad::instances`.
+    pub fn instances(self, instances: &[Instance<S>]) -> Self
+    where
+        S: Clone,
+    {
+        self.map_ty(|ty| ty.instances(instances))
+    }
+
+    /// Apply an affine `out = clamp(in * mult + add, 0, 1)` transform to the quad's final color.
+    /// See `SetColorTransform::color_transform`.
+    pub fn color_transform(self, mult: LinSrgba, add: LinSrgba) -> Self {
+        self.map_ty(|ty| ty.color_transform(mult, add))
+    }
 }
@@ -0,0 +1,90 @@
+//! A per-primitive color transform, applied to a primitive's final color without recomputing its
+//! vertex colors.
+
+use super::LinSrgba;
+
+/// An affine `out = clamp(in * mult + add, 0, 1)` transform applied to a primitive's color.
+///
+/// Composes with whatever produced the input color - a solid fill or a gradient fill - since it
+/// is applied to each resolved vertex color as the very last step before the vertex is submitted
+/// to the mesh.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ColorTransform {
+    pub mult: LinSrgba,
+    pub add: LinSrgba,
+}
+
+impl ColorTransform {
+    /// The identity transform - a `mult` of `1.0` and an `add` of `0.0` on every channel, leaving
+    /// colors unchanged.
+    pub fn identity() -> Self {
+        ColorTransform {
+            mult: LinSrgba::new(1.0, 1.0, 1.0, 1.0),
+            add: LinSrgba::new(0.0, 0.0, 0.0, 0.0),
+        }
+    }
+
+    /// Apply this transform to `color`, clamping each resulting channel to `0.0..=1.0`.
+    pub fn apply(&self, color: LinSrgba) -> LinSrgba {
+        let channel = |c: f32, m: f32, a: f32| (c * m + a).max(0.0).min(1.0);
+        LinSrgba::new(
+            channel(color.red, self.mult.red, self.add.red),
+            channel(color.green, self.mult.green, self.add.green),
+            channel(color.blue, self.mult.blue, self.add.blue),
+            channel(color.alpha, self.mult.alpha, self.add.alpha),
+        )
+    }
+}
+
+impl Default for ColorTransform {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+/// Types that carry a per-primitive `ColorTransform` may use the `SetColorTransform` builder
+/// method, mirroring `SetColor`.
+pub trait SetColorTransform: Sized {
+    /// Provide mutable access to the color transform.
+    fn color_transform_mut(&mut self) -> &mut ColorTransform;
+
+    /// Apply an affine `out = clamp(in * mult + add, 0, 1)` transform to the primitive's final
+    /// color, letting it be tinted, faded or inverted without recomputing its vertex colors.
+    fn color_transform(mut self, mult: LinSrgba, add: LinSrgba) -> Self {
+        let xform = self.color_transform_mut();
+        xform.mult = mult;
+        xform.add = add;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identity_leaves_color_unchanged() {
+        let color = LinSrgba::new(0.2, 0.4, 0.6, 0.8);
+        assert_eq!(ColorTransform::identity().apply(color), color);
+    }
+
+    #[test]
+    fn apply_multiplies_and_adds_per_channel() {
+        let xform = ColorTransform {
+            mult: LinSrgba::new(0.5, 0.5, 0.5, 1.0),
+            add: LinSrgba::new(0.1, 0.0, 0.0, 0.0),
+        };
+        let color = LinSrgba::new(0.4, 0.4, 0.4, 0.4);
+        assert_eq!(xform.apply(color), LinSrgba::new(0.3, 0.2, 0.2, 0.4));
+    }
+
+    #[test]
+    fn apply_clamps_to_0_1() {
+        let xform = ColorTransform {
+            mult: LinSrgba::new(2.0, 1.0, 1.0, 1.0),
+            add: LinSrgba::new(0.0, -2.0, 0.0, 0.0),
+        };
+        let color = LinSrgba::new(1.0, 1.0, 1.0, 1.0);
+        assert_eq!(xform.apply(color), LinSrgba::new(1.0, 0.0, 1.0, 1.0));
+    }
+}
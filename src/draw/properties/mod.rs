@@ -7,6 +7,7 @@
 //! a unique **node::Index** to simplify this.
 
 pub mod color;
+pub mod color_transform;
 pub mod fill;
 pub mod spatial;
 pub mod stroke;
@@ -20,6 +21,7 @@ use std::cell::RefCell;
 use std::ops;
 
 pub use self::color::SetColor;
+pub use self::color_transform::{ColorTransform, SetColorTransform};
 pub use self::fill::SetFill;
 pub use self::spatial::dimension::SetDimensions;
 pub use self::spatial::orientation::SetOrientation;
@@ -360,6 +362,7 @@ where
         let point = Iterator::next(&mut ranges.points);
         let color = Iterator::next(&mut ranges.colors);
         let tex_coords = Iterator::next(&mut ranges.tex_coords);
+        let normal = Iterator::next(&mut ranges.normals);
 
         let point = match point {
             None => return None,
@@ -391,7 +394,17 @@ where
             })
             .unwrap_or_else(draw::mesh::vertex::default_tex_coords);
 
-        Some(draw::mesh::vertex::new(point, color, tex_coords))
+        let normal = normal
+            .map(|normal_ix| {
+                *mesh
+                    .vertex_data
+                    .normals
+                    .get(normal_ix)
+                    .expect("no normal for normal index in IntermediaryMesh")
+            })
+            .unwrap_or_else(draw::mesh::vertex::default_normal);
+
+        Some(draw::mesh::vertex::new(point, color, tex_coords, normal))
     }
 }
 
@@ -0,0 +1,364 @@
+use crate::wgpu;
+
+/// Renders a single, possibly-fractional frame of a `D2Array` image sequence (e.g. one produced
+/// by `load_array_from_image_buffers`), linearly blending between the two nearest layers.
+///
+/// Image-sequence playback that simply floors `current_layer` and samples that one layer reads
+/// as a stepped flip-book at slow playback speeds. `SequencePlayer` instead binds the `floor`ed
+/// and `ceil`ed layers as two separate textures and mixes between them in the fragment shader by
+/// the fractional part of `current_layer`, so slow or fractional speeds read as smooth motion.
+///
+/// Playback wraps at the end of the array - the last layer blends back into the first - and
+/// negative `current_layer` values (e.g. from a negative playback speed) are handled correctly.
+#[derive(Debug)]
+pub struct SequencePlayer {
+    _vs_mod: wgpu::ShaderModule,
+    _fs_mod: wgpu::ShaderModule,
+    bind_group_layout: wgpu::BindGroupLayout,
+    render_pipeline: wgpu::RenderPipeline,
+    sampler: wgpu::Sampler,
+    vertex_buffer: wgpu::Buffer,
+    array_format: wgpu::TextureFormat,
+    layer_count: u32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+struct Vertex {
+    pub position: [f32; 2],
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct Uniforms {
+    mix_factor: f32,
+}
+
+impl SequencePlayer {
+    /// Construct a `SequencePlayer` for an image sequence stored as a `D2Array` texture with
+    /// `layer_count` layers and the given `array_format`.
+    ///
+    /// `dst_sample_count` and `dst_format` describe the destination texture that
+    /// `encode_render_pass` will draw into.
+    pub fn new(
+        device: &wgpu::Device,
+        array_format: wgpu::TextureFormat,
+        layer_count: u32,
+        dst_sample_count: u32,
+        dst_format: wgpu::TextureFormat,
+    ) -> Self {
+        assert!(layer_count > 0, "a `SequencePlayer` requires at least one layer");
+
+        // Load shader modules.
+        let vs = include_bytes!("shaders/array_playback.vert.spv");
+        let vs_spirv = wgpu::read_spirv(std::io::Cursor::new(&vs[..]))
+            .expect("failed to read hard-coded SPIRV");
+        let vs_mod = device.create_shader_module(&vs_spirv);
+        let fs = include_bytes!("shaders/array_playback.frag.spv");
+        let fs_spirv = wgpu::read_spirv(std::io::Cursor::new(&fs[..]))
+            .expect("failed to read hard-coded SPIRV");
+        let fs_mod = device.create_shader_module(&fs_spirv);
+
+        // Create the sampler used for both the `lo` and `hi` layers.
+        let sampler = wgpu::SamplerBuilder::new().build(device);
+
+        // Create the render pipeline.
+        let bind_group_layout = bind_group_layout(device);
+        let pipeline_layout = pipeline_layout(device, &bind_group_layout);
+        let render_pipeline = render_pipeline(
+            device,
+            &pipeline_layout,
+            &vs_mod,
+            &fs_mod,
+            dst_sample_count,
+            dst_format,
+        );
+
+        // Create the vertex buffer.
+        let vertex_buffer = device
+            .create_buffer_mapped(VERTICES.len(), wgpu::BufferUsage::VERTEX)
+            .fill_from_slice(&VERTICES[..]);
+
+        SequencePlayer {
+            _vs_mod: vs_mod,
+            _fs_mod: fs_mod,
+            bind_group_layout,
+            render_pipeline,
+            sampler,
+            vertex_buffer,
+            array_format,
+            layer_count,
+        }
+    }
+
+    /// Given an encoder, submits a render pass command for blending the two layers nearest
+    /// `current_layer` and writing the result to the destination texture.
+    ///
+    /// `current_layer` may be fractional and is wrapped into `0..layer_count`, so both a
+    /// continuously increasing value and a negative playback speed produce valid, looping
+    /// playback.
+    pub fn encode_render_pass(
+        &self,
+        device: &wgpu::Device,
+        array_texture: &wgpu::Texture,
+        current_layer: f32,
+        dst_texture: &wgpu::TextureView,
+        encoder: &mut wgpu::CommandEncoder,
+    ) {
+        let (lo, hi, mix_factor) = lo_hi_mix(current_layer, self.layer_count);
+        let tex_lo = create_layer_texture_view(array_texture, self.array_format, lo);
+        let tex_hi = create_layer_texture_view(array_texture, self.array_format, hi);
+
+        let uniforms = Uniforms { mix_factor };
+        let uniform_buffer = device
+            .create_buffer_mapped(1, wgpu::BufferUsage::UNIFORM)
+            .fill_from_slice(&[uniforms]);
+
+        let bind_group = bind_group(
+            device,
+            &self.bind_group_layout,
+            &tex_lo,
+            &tex_hi,
+            &self.sampler,
+            &uniform_buffer,
+        );
+
+        let vertex_range = 0..VERTICES.len() as u32;
+        let instance_range = 0..1;
+        let render_pass_desc = wgpu::RenderPassDescriptor {
+            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                attachment: dst_texture,
+                resolve_target: None,
+                load_op: wgpu::LoadOp::Clear,
+                store_op: wgpu::StoreOp::Store,
+                clear_color: wgpu::Color::TRANSPARENT,
+            }],
+            depth_stencil_attachment: None,
+        };
+        let mut render_pass = encoder.begin_render_pass(&render_pass_desc);
+        render_pass.set_pipeline(&self.render_pipeline);
+        render_pass.set_vertex_buffers(0, &[(&self.vertex_buffer, 0)]);
+        render_pass.set_bind_group(0, &bind_group, &[]);
+        render_pass.draw(vertex_range, instance_range);
+    }
+}
+
+/// Create a texture view onto a single layer of a `D2Array` texture, suitable for sampling one
+/// frame of an image sequence (e.g. one produced by `load_array_from_image_buffers`) on its own.
+pub fn create_layer_texture_view(
+    texture: &wgpu::Texture,
+    format: wgpu::TextureFormat,
+    layer: u32,
+) -> wgpu::TextureView {
+    let desc = wgpu::TextureViewDescriptor {
+        format,
+        dimension: wgpu::TextureViewDimension::D2,
+        aspect: wgpu::TextureAspect::All,
+        base_mip_level: 0,
+        level_count: 1,
+        base_array_layer: layer,
+        array_layer_count: 1,
+    };
+    texture.create_view(&desc)
+}
+
+// From a float `current_layer` and the array's `layer_count`, compute the `lo` and `hi` layer
+// indices to blend between and the fractional mix factor between them.
+//
+// `rem_euclid` wraps `current_layer` into the non-negative `0..layer_count` range before
+// flooring, so a negative `current_layer` (e.g. from a negative playback speed) still produces a
+// valid `lo` index and a non-negative `fract`. `hi` wraps back around to `0` when `lo` is the
+// last layer, so the last frame blends back into the first.
+fn lo_hi_mix(current_layer: f32, layer_count: u32) -> (u32, u32, f32) {
+    let wrapped = current_layer.rem_euclid(layer_count as f32);
+    let lo = wrapped.floor() as u32 % layer_count;
+    let hi = (lo + 1) % layer_count;
+    let mix_factor = wrapped.fract();
+    (lo, hi, mix_factor)
+}
+
+const VERTICES: [Vertex; 4] = [
+    Vertex {
+        position: [-1.0, -1.0],
+    },
+    Vertex {
+        position: [-1.0, 1.0],
+    },
+    Vertex {
+        position: [1.0, -1.0],
+    },
+    Vertex {
+        position: [1.0, 1.0],
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lo_hi_mix_at_layer_start_has_zero_mix_factor() {
+        assert_eq!(lo_hi_mix(0.0, 4), (0, 1, 0.0));
+        assert_eq!(lo_hi_mix(2.0, 4), (2, 3, 0.0));
+    }
+
+    #[test]
+    fn lo_hi_mix_fractional_layer_splits_lo_and_hi() {
+        let (lo, hi, mix) = lo_hi_mix(1.25, 4);
+        assert_eq!((lo, hi), (1, 2));
+        assert!((mix - 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn lo_hi_mix_wraps_the_last_layer_back_to_the_first() {
+        let (lo, hi, mix) = lo_hi_mix(3.5, 4);
+        assert_eq!((lo, hi), (3, 0));
+        assert!((mix - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn lo_hi_mix_handles_negative_current_layer() {
+        let (lo, hi, mix) = lo_hi_mix(-0.25, 4);
+        assert_eq!((lo, hi), (3, 0));
+        assert!((mix - 0.75).abs() < 1e-6);
+    }
+}
+
+fn vertex_attrs() -> [wgpu::VertexAttributeDescriptor; 1] {
+    [wgpu::VertexAttributeDescriptor {
+        format: wgpu::VertexFormat::Float2,
+        offset: 0,
+        shader_location: 0,
+    }]
+}
+
+fn bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+    let tex_lo_binding = wgpu::BindGroupLayoutBinding {
+        binding: 0,
+        visibility: wgpu::ShaderStage::FRAGMENT,
+        ty: wgpu::BindingType::SampledTexture {
+            multisampled: false,
+            dimension: wgpu::TextureViewDimension::D2,
+        },
+    };
+    let tex_hi_binding = wgpu::BindGroupLayoutBinding {
+        binding: 1,
+        visibility: wgpu::ShaderStage::FRAGMENT,
+        ty: wgpu::BindingType::SampledTexture {
+            multisampled: false,
+            dimension: wgpu::TextureViewDimension::D2,
+        },
+    };
+    let sampler_binding = wgpu::BindGroupLayoutBinding {
+        binding: 2,
+        visibility: wgpu::ShaderStage::FRAGMENT,
+        ty: wgpu::BindingType::Sampler,
+    };
+    let uniforms_binding = wgpu::BindGroupLayoutBinding {
+        binding: 3,
+        visibility: wgpu::ShaderStage::FRAGMENT,
+        ty: wgpu::BindingType::UniformBuffer { dynamic: false },
+    };
+    let bindings = &[
+        tex_lo_binding,
+        tex_hi_binding,
+        sampler_binding,
+        uniforms_binding,
+    ];
+    let desc = wgpu::BindGroupLayoutDescriptor { bindings };
+    device.create_bind_group_layout(&desc)
+}
+
+fn bind_group(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    tex_lo: &wgpu::TextureView,
+    tex_hi: &wgpu::TextureView,
+    sampler: &wgpu::Sampler,
+    uniform_buffer: &wgpu::Buffer,
+) -> wgpu::BindGroup {
+    let tex_lo_binding = wgpu::Binding {
+        binding: 0,
+        resource: wgpu::BindingResource::TextureView(tex_lo),
+    };
+    let tex_hi_binding = wgpu::Binding {
+        binding: 1,
+        resource: wgpu::BindingResource::TextureView(tex_hi),
+    };
+    let sampler_binding = wgpu::Binding {
+        binding: 2,
+        resource: wgpu::BindingResource::Sampler(sampler),
+    };
+    let uniforms_binding = wgpu::Binding {
+        binding: 3,
+        resource: wgpu::BindingResource::Buffer {
+            buffer: uniform_buffer,
+            range: 0..std::mem::size_of::<Uniforms>() as wgpu::BufferAddress,
+        },
+    };
+    let bindings = &[tex_lo_binding, tex_hi_binding, sampler_binding, uniforms_binding];
+    let desc = wgpu::BindGroupDescriptor { layout, bindings };
+    device.create_bind_group(&desc)
+}
+
+fn pipeline_layout(
+    device: &wgpu::Device,
+    bind_group_layout: &wgpu::BindGroupLayout,
+) -> wgpu::PipelineLayout {
+    let desc = wgpu::PipelineLayoutDescriptor {
+        bind_group_layouts: &[&bind_group_layout],
+    };
+    device.create_pipeline_layout(&desc)
+}
+
+fn render_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    vs_mod: &wgpu::ShaderModule,
+    fs_mod: &wgpu::ShaderModule,
+    dst_sample_count: u32,
+    dst_format: wgpu::TextureFormat,
+) -> wgpu::RenderPipeline {
+    let vs_desc = wgpu::ProgrammableStageDescriptor {
+        module: &vs_mod,
+        entry_point: "main",
+    };
+    let fs_desc = wgpu::ProgrammableStageDescriptor {
+        module: &fs_mod,
+        entry_point: "main",
+    };
+    let raster_desc = wgpu::RasterizationStateDescriptor {
+        front_face: wgpu::FrontFace::Ccw,
+        cull_mode: wgpu::CullMode::None,
+        depth_bias: 0,
+        depth_bias_slope_scale: 0.0,
+        depth_bias_clamp: 0.0,
+    };
+    let color_state_desc = wgpu::ColorStateDescriptor {
+        format: dst_format,
+        color_blend: wgpu::BlendDescriptor::REPLACE,
+        alpha_blend: wgpu::BlendDescriptor::REPLACE,
+        write_mask: wgpu::ColorWrite::ALL,
+    };
+    let vertex_attrs = vertex_attrs();
+    let vertex_buffer_desc = wgpu::VertexBufferDescriptor {
+        stride: std::mem::size_of::<Vertex>() as wgpu::BufferAddress,
+        step_mode: wgpu::InputStepMode::Vertex,
+        attributes: &vertex_attrs[..],
+    };
+    let desc = wgpu::RenderPipelineDescriptor {
+        layout,
+        vertex_stage: vs_desc,
+        fragment_stage: Some(fs_desc),
+        rasterization_state: Some(raster_desc),
+        primitive_topology: wgpu::PrimitiveTopology::TriangleStrip,
+        color_states: &[color_state_desc],
+        depth_stencil_state: None,
+        index_format: wgpu::IndexFormat::Uint16,
+        vertex_buffers: &[vertex_buffer_desc],
+        sample_count: dst_sample_count,
+        sample_mask: !0,
+        alpha_to_coverage_enabled: false,
+    };
+    device.create_render_pipeline(&desc)
+}
@@ -6,6 +6,12 @@ use crate::wgpu;
 /// The `src_texture` must have the `TextureUsage::SAMPLED` enabled.
 ///
 /// The `dst_texture` must have the `TextureUsage::OUTPUT_ATTACHMENT` enabled.
+///
+/// By default the source is assumed to be a plain `D2` view. Use `Builder::src_dimension` and
+/// `Builder::src_array_layer` to reshape a single layer of a `D2Array` or a single face of a
+/// `Cube` source instead - via `Builder::build` for a color destination or `Builder::build_depth`
+/// for a depth destination. To write into a specific layer of an array destination, simply pass a
+/// `dst_texture` view created with the desired `base_array_layer`.
 #[derive(Debug)]
 pub struct Reshaper {
     _vs_mod: wgpu::ShaderModule,
@@ -16,6 +22,61 @@ pub struct Reshaper {
     sampler: wgpu::Sampler,
     uniform_buffer: Option<wgpu::Buffer>,
     vertex_buffer: wgpu::Buffer,
+    kind: Kind,
+    compute: Option<ComputeResources>,
+}
+
+// The resources required for the alternative `encode_compute_pass` resolve path.
+//
+// Unlike the render-pass path, the destination is a storage texture rather than an output
+// attachment, so its bind group can't be known until `encode_compute_pass` is given the
+// destination view - we build a fresh bind group for it on each call.
+#[derive(Debug)]
+struct ComputeResources {
+    _cs_mod: wgpu::ShaderModule,
+    bind_group_layout: wgpu::BindGroupLayout,
+    pipeline: wgpu::ComputePipeline,
+    uniform_buffer: wgpu::Buffer,
+}
+
+// Whether this `Reshaper` resolves to a color attachment or collapses an MSAA depth buffer down
+// to a single-sampled depth attachment.
+#[derive(Clone, Copy, Debug)]
+enum Kind {
+    Color,
+    Depth,
+}
+
+/// The set of tone-mapping operators supported when blitting from an HDR intermediary texture
+/// down to the destination format.
+///
+/// Tone-mapping is applied in the resolve fragment shader after averaging the source samples and
+/// multiplying by `exposure`, and before the result is written to `dst_format`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ToneMap {
+    /// Write the exposed color through with no tone-mapping curve applied.
+    None,
+    /// The classic `c / (1 + c)` operator, applied per-channel.
+    Reinhard,
+    /// The Narkowicz fit to the ACES filmic curve, applied per-channel.
+    AcesFilmic,
+}
+
+impl ToneMap {
+    // The `tone_map_mode` value expected by the `Uniforms` struct and resolve shaders.
+    fn mode(&self) -> u32 {
+        match self {
+            ToneMap::None => 0,
+            ToneMap::Reinhard => 1,
+            ToneMap::AcesFilmic => 2,
+        }
+    }
+}
+
+impl Default for ToneMap {
+    fn default() -> Self {
+        ToneMap::None
+    }
 }
 
 #[repr(C)]
@@ -28,30 +89,265 @@ struct Vertex {
 #[derive(Copy, Clone)]
 struct Uniforms {
     sample_count: u32,
+    tone_map_mode: u32,
+    exposure: f32,
+    src_array_layer: u32,
+}
+
+/// A builder for configuring and constructing a `Reshaper`.
+#[derive(Clone, Debug)]
+pub struct Builder {
+    tone_map: ToneMap,
+    exposure: f32,
+    compute: bool,
+    src_dimension: wgpu::TextureViewDimension,
+    src_array_layer: Option<u32>,
+}
+
+impl Builder {
+    /// Begin building a `Reshaper` with the default tone-mapping settings (`ToneMap::None` and an
+    /// exposure of `1.0`) and a `D2` source dimension.
+    pub fn new() -> Self {
+        Builder {
+            tone_map: ToneMap::None,
+            exposure: 1.0,
+            compute: false,
+            src_dimension: wgpu::TextureViewDimension::D2,
+            src_array_layer: None,
+        }
+    }
+
+    /// The dimension of the `src_texture` view.
+    ///
+    /// Use `D2Array` or `Cube` to reshape a single layer or face of a layered source - see
+    /// `src_array_layer`.
+    pub fn src_dimension(mut self, src_dimension: wgpu::TextureViewDimension) -> Self {
+        self.src_dimension = src_dimension;
+        self
+    }
+
+    /// For a `D2Array` or `Cube` (or `CubeArray`) `src_dimension`, the specific array layer or
+    /// cube face to sample from.
+    pub fn src_array_layer(mut self, src_array_layer: u32) -> Self {
+        self.src_array_layer = Some(src_array_layer);
+        self
+    }
+
+    /// The tone-mapping operator to apply to the resolved, exposed color before it is written to
+    /// the destination texture.
+    pub fn tone_map(mut self, tone_map: ToneMap) -> Self {
+        self.tone_map = tone_map;
+        self
+    }
+
+    /// The multiplier applied to the resolved color before the tone-mapping curve is applied.
+    pub fn exposure(mut self, exposure: f32) -> Self {
+        self.exposure = exposure;
+        self
+    }
+
+    /// Also prepare the `encode_compute_pass` resolve path, which writes directly to a storage
+    /// texture instead of requiring a render pass and an `OUTPUT_ATTACHMENT` destination.
+    ///
+    /// Check `supports_storage_texture_format` against the destination texture's format before
+    /// passing `true` - not every format that works as an `OUTPUT_ATTACHMENT` can also be bound
+    /// as a storage texture, and `encode_compute_pass` has no way to re-check this itself since
+    /// it's only ever given a `TextureView`, not the format it was created with.
+    pub fn compute(mut self, compute: bool) -> Self {
+        self.compute = compute;
+        self
+    }
+
+    /// Build the `Reshaper` with the settings specified on this `Builder`.
+    pub fn build(
+        self,
+        device: &wgpu::Device,
+        src_texture: &wgpu::TextureView,
+        src_sample_count: u32,
+        dst_sample_count: u32,
+        dst_format: wgpu::TextureFormat,
+    ) -> Reshaper {
+        Reshaper::new_inner_kind_compute(
+            device,
+            src_texture,
+            src_sample_count,
+            dst_sample_count,
+            dst_format,
+            self.tone_map,
+            self.exposure,
+            Kind::Color,
+            self.compute,
+            self.src_dimension,
+            self.src_array_layer,
+        )
+    }
+
+    /// Build a depth-resolving `Reshaper` with the settings specified on this `Builder`, ready for
+    /// `encode_depth_render_pass` instead of `encode_render_pass`.
+    ///
+    /// Unlike `Reshaper::new_depth`, this respects `src_dimension`/`src_array_layer`, so it can
+    /// resolve a single layer of a `D2Array` or a single face of a `Cube` depth source (e.g. one
+    /// face of a cubemap shadow atlas).
+    pub fn build_depth(
+        self,
+        device: &wgpu::Device,
+        src_texture: &wgpu::TextureView,
+        src_sample_count: u32,
+        dst_sample_count: u32,
+        dst_format: wgpu::TextureFormat,
+    ) -> Reshaper {
+        Reshaper::new_inner_kind_compute(
+            device,
+            src_texture,
+            src_sample_count,
+            dst_sample_count,
+            dst_format,
+            self.tone_map,
+            self.exposure,
+            Kind::Depth,
+            self.compute,
+            self.src_dimension,
+            self.src_array_layer,
+        )
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Reshaper {
-    /// Construct a new `Reshaper`.
+    /// Construct a new `Reshaper` with no tone-mapping applied.
+    ///
+    /// Use `Reshaper::builder` for a version of this constructor that allows for selecting a
+    /// `ToneMap` operator and `exposure`.
     pub fn new(
         device: &wgpu::Device,
         src_texture: &wgpu::TextureView,
         src_sample_count: u32,
         dst_sample_count: u32,
         dst_format: wgpu::TextureFormat,
+    ) -> Self {
+        Self::new_inner(
+            device,
+            src_texture,
+            src_sample_count,
+            dst_sample_count,
+            dst_format,
+            ToneMap::None,
+            1.0,
+        )
+    }
+
+    /// Begin building a `Reshaper`, allowing for a `ToneMap` operator and `exposure` to be
+    /// specified before constructing the final swapchain-format blit pass.
+    pub fn builder() -> Builder {
+        Builder::new()
+    }
+
+    /// Construct a `Reshaper` that collapses a multisampled depth texture (e.g. `Depth32Float`)
+    /// down to a single-sampled depth texture.
+    ///
+    /// The `src_texture` must be a depth format with `TextureUsage::SAMPLED` enabled and the
+    /// `dst_format` must be a depth format with `TextureUsage::OUTPUT_ATTACHMENT` enabled.
+    ///
+    /// Rather than averaging subsamples as the color path does, the **minimum** (i.e. closest)
+    /// depth across all subsamples is written, reusing the same unrolled-sample-count shader
+    /// selection scheme as the color path.
+    ///
+    /// Assumes a plain `D2` source. Use `Builder::build_depth` to resolve a single layer of a
+    /// `D2Array` or a single face of a `Cube` source instead (e.g. one face of a cubemap shadow
+    /// atlas).
+    pub fn new_depth(
+        device: &wgpu::Device,
+        src_texture: &wgpu::TextureView,
+        src_sample_count: u32,
+        dst_sample_count: u32,
+        dst_format: wgpu::TextureFormat,
+    ) -> Self {
+        Self::new_inner_kind(
+            device,
+            src_texture,
+            src_sample_count,
+            dst_sample_count,
+            dst_format,
+            ToneMap::None,
+            1.0,
+            Kind::Depth,
+        )
+    }
+
+    fn new_inner(
+        device: &wgpu::Device,
+        src_texture: &wgpu::TextureView,
+        src_sample_count: u32,
+        dst_sample_count: u32,
+        dst_format: wgpu::TextureFormat,
+        tone_map: ToneMap,
+        exposure: f32,
+    ) -> Self {
+        Self::new_inner_kind(
+            device,
+            src_texture,
+            src_sample_count,
+            dst_sample_count,
+            dst_format,
+            tone_map,
+            exposure,
+            Kind::Color,
+        )
+    }
+
+    fn new_inner_kind(
+        device: &wgpu::Device,
+        src_texture: &wgpu::TextureView,
+        src_sample_count: u32,
+        dst_sample_count: u32,
+        dst_format: wgpu::TextureFormat,
+        tone_map: ToneMap,
+        exposure: f32,
+        kind: Kind,
+    ) -> Self {
+        Self::new_inner_kind_compute(
+            device,
+            src_texture,
+            src_sample_count,
+            dst_sample_count,
+            dst_format,
+            tone_map,
+            exposure,
+            kind,
+            false,
+            wgpu::TextureViewDimension::D2,
+            None,
+        )
+    }
+
+    fn new_inner_kind_compute(
+        device: &wgpu::Device,
+        src_texture: &wgpu::TextureView,
+        src_sample_count: u32,
+        dst_sample_count: u32,
+        dst_format: wgpu::TextureFormat,
+        tone_map: ToneMap,
+        exposure: f32,
+        kind: Kind,
+        compute: bool,
+        src_dimension: wgpu::TextureViewDimension,
+        src_array_layer: Option<u32>,
     ) -> Self {
         // Load shader modules.
         let vs = include_bytes!("shaders/vert.spv");
         let vs_spirv = wgpu::read_spirv(std::io::Cursor::new(&vs[..]))
             .expect("failed to read hard-coded SPIRV");
         let vs_mod = device.create_shader_module(&vs_spirv);
-        let fs = match src_sample_count {
-            1 => &include_bytes!("shaders/frag.spv")[..],
-            2 => &include_bytes!("shaders/frag_msaa2.spv")[..],
-            4 => &include_bytes!("shaders/frag_msaa4.spv")[..],
-            8 => &include_bytes!("shaders/frag_msaa8.spv")[..],
-            16 => &include_bytes!("shaders/frag_msaa16.spv")[..],
-            _ => &include_bytes!("shaders/frag_msaa.spv")[..],
-        };
+        let fs = fs_bytes(
+            src_sample_count,
+            kind,
+            needs_uniforms(src_sample_count, tone_map, exposure, src_array_layer),
+        );
         let fs_spirv =
             wgpu::read_spirv(std::io::Cursor::new(fs)).expect("failed to read hard-coded SPIRV");
         let fs_mod = device.create_shader_module(&fs_spirv);
@@ -60,7 +356,14 @@ impl Reshaper {
         let sampler = wgpu::SamplerBuilder::new().build(device);
 
         // Create the render pipeline.
-        let bind_group_layout = bind_group_layout(device, src_sample_count);
+        let bind_group_layout = bind_group_layout(
+            device,
+            src_sample_count,
+            tone_map,
+            exposure,
+            src_dimension,
+            src_array_layer,
+        );
         let pipeline_layout = pipeline_layout(device, &bind_group_layout);
         let render_pipeline = render_pipeline(
             device,
@@ -69,15 +372,25 @@ impl Reshaper {
             &fs_mod,
             dst_sample_count,
             dst_format,
+            kind,
         );
 
-        // Create the uniform buffer to pass the sample count if we don't have an unrolled resolve
-        // fragment shader for it.
-        let uniform_buffer = match unrolled_sample_count(src_sample_count) {
-            true => None,
-            false => {
+        // Create the uniform buffer to pass the sample count, exposure, tone-mapping mode and
+        // source array layer.
+        //
+        // We always need this when tone-mapping is enabled (even for an unrolled sample count,
+        // whose shader otherwise requires no uniforms) as the exposure and tone-mapping mode must
+        // still reach the fragment shader. The same applies whenever a non-default `exposure` is
+        // given or a specific `src_array_layer` has been selected from a `D2Array` or `Cube`
+        // source.
+        let uniform_buffer = match needs_uniforms(src_sample_count, tone_map, exposure, src_array_layer) {
+            false => None,
+            true => {
                 let uniforms = Uniforms {
                     sample_count: src_sample_count,
+                    tone_map_mode: tone_map.mode(),
+                    exposure,
+                    src_array_layer: src_array_layer.unwrap_or(0),
                 };
                 let buffer = device
                     .create_buffer_mapped(1, wgpu::BufferUsage::UNIFORM)
@@ -100,6 +413,12 @@ impl Reshaper {
             .create_buffer_mapped(VERTICES.len(), wgpu::BufferUsage::VERTEX)
             .fill_from_slice(&VERTICES[..]);
 
+        // Optionally prepare the compute-shader resolve path.
+        let compute = match compute {
+            false => None,
+            true => Some(compute_resources(device, src_sample_count)),
+        };
+
         Reshaper {
             _vs_mod: vs_mod,
             _fs_mod: fs_mod,
@@ -109,16 +428,26 @@ impl Reshaper {
             sampler,
             uniform_buffer,
             vertex_buffer,
+            kind,
+            compute,
         }
     }
 
     /// Given an encoder, submits a render pass command for writing the source texture to the
-    /// destination texture.
+    /// destination color texture.
+    ///
+    /// Panics if this `Reshaper` was constructed via `new_depth` or `Builder::build_depth` - use
+    /// `encode_depth_render_pass` instead.
     pub fn encode_render_pass(
         &self,
         dst_texture: &wgpu::TextureView,
         encoder: &mut wgpu::CommandEncoder,
     ) {
+        assert!(
+            matches!(self.kind, Kind::Color),
+            "`encode_render_pass` may only be called on a `Reshaper` constructed for a color \
+             destination - use `encode_depth_render_pass` instead",
+        );
         let vertex_range = 0..VERTICES.len() as u32;
         let instance_range = 0..1;
 
@@ -138,8 +467,83 @@ impl Reshaper {
         render_pass.set_bind_group(0, &self.bind_group, &[]);
         render_pass.draw(vertex_range, instance_range);
     }
+
+    /// Given an encoder, submits a render pass command for collapsing the multisampled source
+    /// depth texture down to the single-sampled destination depth texture.
+    ///
+    /// Only valid for a `Reshaper` constructed via `new_depth` or `Builder::build_depth`.
+    pub fn encode_depth_render_pass(
+        &self,
+        dst_depth_texture: &wgpu::TextureView,
+        encoder: &mut wgpu::CommandEncoder,
+    ) {
+        assert!(
+            matches!(self.kind, Kind::Depth),
+            "`encode_depth_render_pass` may only be called on a `Reshaper` constructed via \
+             `new_depth` or `Builder::build_depth`",
+        );
+        let vertex_range = 0..VERTICES.len() as u32;
+        let instance_range = 0..1;
+
+        let render_pass_desc = wgpu::RenderPassDescriptor {
+            color_attachments: &[],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachmentDescriptor {
+                attachment: dst_depth_texture,
+                depth_load_op: wgpu::LoadOp::Clear,
+                depth_store_op: wgpu::StoreOp::Store,
+                clear_depth: 1.0,
+                stencil_load_op: wgpu::LoadOp::Clear,
+                stencil_store_op: wgpu::StoreOp::Store,
+                clear_stencil: 0,
+            }),
+        };
+        let mut render_pass = encoder.begin_render_pass(&render_pass_desc);
+        render_pass.set_pipeline(&self.render_pipeline);
+        render_pass.set_vertex_buffers(0, &[(&self.vertex_buffer, 0)]);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.draw(vertex_range, instance_range);
+    }
+
+    /// Resolve the multisampled source texture directly into a storage texture via a compute
+    /// pass, rather than a render pass.
+    ///
+    /// Avoids the full-screen-triangle render-pass path entirely and handles any `src_sample_count`
+    /// with a single shader. `dst_texture` must have been created with `TextureUsage::STORAGE`
+    /// enabled and `dst_size` must match its dimensions.
+    ///
+    /// Only valid for a `Reshaper` constructed with `Builder::compute(true)`.
+    pub fn encode_compute_pass(
+        &self,
+        device: &wgpu::Device,
+        src_texture: &wgpu::TextureView,
+        dst_texture: &wgpu::TextureView,
+        dst_size: (u32, u32),
+        encoder: &mut wgpu::CommandEncoder,
+    ) {
+        let compute = self
+            .compute
+            .as_ref()
+            .expect("`encode_compute_pass` requires a `Reshaper` built with `Builder::compute(true)`");
+        let bind_group = compute_bind_group(
+            device,
+            &compute.bind_group_layout,
+            src_texture,
+            dst_texture,
+            &compute.uniform_buffer,
+        );
+        let mut compute_pass = encoder.begin_compute_pass();
+        compute_pass.set_pipeline(&compute.pipeline);
+        compute_pass.set_bind_group(0, &bind_group, &[]);
+        let (dst_w, dst_h) = dst_size;
+        let workgroups_x = (dst_w + TILE_SIZE - 1) / TILE_SIZE;
+        let workgroups_y = (dst_h + TILE_SIZE - 1) / TILE_SIZE;
+        compute_pass.dispatch(workgroups_x, workgroups_y, 1);
+    }
 }
 
+// The width and height, in pixels, of the tile that each compute workgroup resolves.
+const TILE_SIZE: u32 = 8;
+
 const VERTICES: [Vertex; 4] = [
     Vertex {
         position: [-1.0, -1.0],
@@ -163,6 +567,64 @@ fn unrolled_sample_count(sample_count: u32) -> bool {
     }
 }
 
+// Whether or not the given tone-mapping operator requires no uniforms (i.e. is a no-op).
+fn tone_map_disabled(tone_map: ToneMap) -> bool {
+    tone_map == ToneMap::None
+}
+
+// Whether or not the resolve pass requires the `Uniforms` buffer and its bind group binding.
+//
+// This is the case whenever the sample count isn't one of our unrolled shader variants, when a
+// tone-mapping operator is enabled, when `exposure` isn't `1.0`, or when a specific source array
+// layer has been selected.
+fn needs_uniforms(
+    src_sample_count: u32,
+    tone_map: ToneMap,
+    exposure: f32,
+    src_array_layer: Option<u32>,
+) -> bool {
+    !unrolled_sample_count(src_sample_count)
+        || !tone_map_disabled(tone_map)
+        || exposure != 1.0
+        || src_array_layer.is_some()
+}
+
+// Select the pre-compiled fragment shader bytes for the given source sample count and `Kind`.
+//
+// The depth path mirrors the unrolled-sample-count scheme used for color (see
+// `resolve_depth.frag.glsl`/`resolve_depth_layered.frag.glsl` in `shaders/`), but writes the
+// minimum (closest) subsample depth to `gl_FragDepth` rather than averaging.
+//
+// Whenever `needs_uniforms` is true - a non-default `ToneMap`, a non-default `exposure`, or a
+// `src_array_layer` was requested, or `src_sample_count` isn't one of the unrolled variants below
+// - we can't reuse the plain, unrolled shaders: they're compiled with no uniform buffer bound at
+// all, so they have no way to read `tone_map_mode`/`exposure`/`src_array_layer` even if the Rust
+// side populates them. Route to the generic, uniform-driven variant instead (`frag_tonemap.spv` /
+// `frag_depth_layered.spv`), which loops over `sample_count` at runtime and, for color, applies
+// the requested tone-mapping curve.
+fn fs_bytes(src_sample_count: u32, kind: Kind, needs_uniforms: bool) -> &'static [u8] {
+    match (kind, needs_uniforms) {
+        (Kind::Color, true) => &include_bytes!("shaders/frag_tonemap.spv")[..],
+        (Kind::Color, false) => match src_sample_count {
+            1 => &include_bytes!("shaders/frag.spv")[..],
+            2 => &include_bytes!("shaders/frag_msaa2.spv")[..],
+            4 => &include_bytes!("shaders/frag_msaa4.spv")[..],
+            8 => &include_bytes!("shaders/frag_msaa8.spv")[..],
+            16 => &include_bytes!("shaders/frag_msaa16.spv")[..],
+            _ => &include_bytes!("shaders/frag_msaa.spv")[..],
+        },
+        (Kind::Depth, true) => &include_bytes!("shaders/frag_depth_layered.spv")[..],
+        (Kind::Depth, false) => match src_sample_count {
+            1 => &include_bytes!("shaders/frag_depth.spv")[..],
+            2 => &include_bytes!("shaders/frag_depth_msaa2.spv")[..],
+            4 => &include_bytes!("shaders/frag_depth_msaa4.spv")[..],
+            8 => &include_bytes!("shaders/frag_depth_msaa8.spv")[..],
+            16 => &include_bytes!("shaders/frag_depth_msaa16.spv")[..],
+            _ => &include_bytes!("shaders/frag_depth_msaa.spv")[..],
+        },
+    }
+}
+
 fn vertex_attrs() -> [wgpu::VertexAttributeDescriptor; 1] {
     [wgpu::VertexAttributeDescriptor {
         format: wgpu::VertexFormat::Float2,
@@ -171,13 +633,20 @@ fn vertex_attrs() -> [wgpu::VertexAttributeDescriptor; 1] {
     }]
 }
 
-fn bind_group_layout(device: &wgpu::Device, src_sample_count: u32) -> wgpu::BindGroupLayout {
+fn bind_group_layout(
+    device: &wgpu::Device,
+    src_sample_count: u32,
+    tone_map: ToneMap,
+    exposure: f32,
+    src_dimension: wgpu::TextureViewDimension,
+    src_array_layer: Option<u32>,
+) -> wgpu::BindGroupLayout {
     let texture_binding = wgpu::BindGroupLayoutBinding {
         binding: 0,
         visibility: wgpu::ShaderStage::FRAGMENT,
         ty: wgpu::BindingType::SampledTexture {
             multisampled: src_sample_count > 1,
-            dimension: wgpu::TextureViewDimension::D2,
+            dimension: src_dimension,
         },
     };
     let sampler_binding = wgpu::BindGroupLayoutBinding {
@@ -185,9 +654,9 @@ fn bind_group_layout(device: &wgpu::Device, src_sample_count: u32) -> wgpu::Bind
         visibility: wgpu::ShaderStage::FRAGMENT,
         ty: wgpu::BindingType::Sampler,
     };
-    let uniforms_binding = match unrolled_sample_count(src_sample_count) {
-        true => None,
-        false => Some(wgpu::BindGroupLayoutBinding {
+    let uniforms_binding = match needs_uniforms(src_sample_count, tone_map, exposure, src_array_layer) {
+        false => None,
+        true => Some(wgpu::BindGroupLayoutBinding {
             binding: 2,
             visibility: wgpu::ShaderStage::FRAGMENT,
             ty: wgpu::BindingType::UniformBuffer { dynamic: false },
@@ -246,6 +715,121 @@ fn pipeline_layout(
     device.create_pipeline_layout(&desc)
 }
 
+/// Whether `format` can be bound as a storage texture, as `encode_compute_pass`'s destination
+/// must be when a `Reshaper` is built with `Builder::compute(true)`.
+///
+/// Most sampled/render-attachment formats can't also be bound for storage - check this before
+/// enabling the compute path rather than letting bind group creation fail inside `wgpu`.
+pub fn supports_storage_texture_format(format: wgpu::TextureFormat) -> bool {
+    matches!(
+        format,
+        wgpu::TextureFormat::Rgba8Unorm
+            | wgpu::TextureFormat::Rgba8Uint
+            | wgpu::TextureFormat::Rgba16Float
+            | wgpu::TextureFormat::Rgba32Float
+    )
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+struct ComputeUniforms {
+    sample_count: u32,
+}
+
+fn compute_resources(device: &wgpu::Device, src_sample_count: u32) -> ComputeResources {
+    let cs = include_bytes!("shaders/resolve.comp.spv");
+    let cs_spirv =
+        wgpu::read_spirv(std::io::Cursor::new(&cs[..])).expect("failed to read hard-coded SPIRV");
+    let cs_mod = device.create_shader_module(&cs_spirv);
+
+    let bind_group_layout = compute_bind_group_layout(device, src_sample_count);
+    let pipeline_layout = pipeline_layout(device, &bind_group_layout);
+    let pipeline = compute_pipeline(device, &pipeline_layout, &cs_mod);
+
+    let uniforms = ComputeUniforms {
+        sample_count: src_sample_count,
+    };
+    let uniform_buffer = device
+        .create_buffer_mapped(1, wgpu::BufferUsage::UNIFORM)
+        .fill_from_slice(&[uniforms]);
+
+    ComputeResources {
+        _cs_mod: cs_mod,
+        bind_group_layout,
+        pipeline,
+        uniform_buffer,
+    }
+}
+
+fn compute_bind_group_layout(device: &wgpu::Device, src_sample_count: u32) -> wgpu::BindGroupLayout {
+    let src_binding = wgpu::BindGroupLayoutBinding {
+        binding: 0,
+        visibility: wgpu::ShaderStage::COMPUTE,
+        ty: wgpu::BindingType::SampledTexture {
+            multisampled: src_sample_count > 1,
+            dimension: wgpu::TextureViewDimension::D2,
+        },
+    };
+    let dst_binding = wgpu::BindGroupLayoutBinding {
+        binding: 1,
+        visibility: wgpu::ShaderStage::COMPUTE,
+        ty: wgpu::BindingType::StorageTexture {
+            dimension: wgpu::TextureViewDimension::D2,
+        },
+    };
+    let uniforms_binding = wgpu::BindGroupLayoutBinding {
+        binding: 2,
+        visibility: wgpu::ShaderStage::COMPUTE,
+        ty: wgpu::BindingType::UniformBuffer { dynamic: false },
+    };
+    let bindings = &[src_binding, dst_binding, uniforms_binding];
+    let desc = wgpu::BindGroupLayoutDescriptor { bindings };
+    device.create_bind_group_layout(&desc)
+}
+
+fn compute_bind_group(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    src_texture: &wgpu::TextureView,
+    dst_texture: &wgpu::TextureView,
+    uniform_buffer: &wgpu::Buffer,
+) -> wgpu::BindGroup {
+    let src_binding = wgpu::Binding {
+        binding: 0,
+        resource: wgpu::BindingResource::TextureView(src_texture),
+    };
+    let dst_binding = wgpu::Binding {
+        binding: 1,
+        resource: wgpu::BindingResource::TextureView(dst_texture),
+    };
+    let uniforms_binding = wgpu::Binding {
+        binding: 2,
+        resource: wgpu::BindingResource::Buffer {
+            buffer: uniform_buffer,
+            range: 0..std::mem::size_of::<ComputeUniforms>() as wgpu::BufferAddress,
+        },
+    };
+    let bindings = &[src_binding, dst_binding, uniforms_binding];
+    let desc = wgpu::BindGroupDescriptor { layout, bindings };
+    device.create_bind_group(&desc)
+}
+
+fn compute_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    cs_mod: &wgpu::ShaderModule,
+) -> wgpu::ComputePipeline {
+    let cs_desc = wgpu::ProgrammableStageDescriptor {
+        module: cs_mod,
+        entry_point: "main",
+    };
+    let desc = wgpu::ComputePipelineDescriptor {
+        layout,
+        compute_stage: cs_desc,
+    };
+    device.create_compute_pipeline(&desc)
+}
+
 fn render_pipeline(
     device: &wgpu::Device,
     layout: &wgpu::PipelineLayout,
@@ -253,6 +837,7 @@ fn render_pipeline(
     fs_mod: &wgpu::ShaderModule,
     dst_sample_count: u32,
     dst_format: wgpu::TextureFormat,
+    kind: Kind,
 ) -> wgpu::RenderPipeline {
     let vs_desc = wgpu::ProgrammableStageDescriptor {
         module: &vs_mod,
@@ -275,6 +860,22 @@ fn render_pipeline(
         alpha_blend: wgpu::BlendDescriptor::REPLACE,
         write_mask: wgpu::ColorWrite::ALL,
     };
+    let depth_stencil_state_desc = match kind {
+        Kind::Color => None,
+        Kind::Depth => Some(wgpu::DepthStencilStateDescriptor {
+            format: dst_format,
+            depth_write_enabled: true,
+            depth_compare: wgpu::CompareFunction::Always,
+            stencil_front: wgpu::StencilStateFaceDescriptor::IGNORE,
+            stencil_back: wgpu::StencilStateFaceDescriptor::IGNORE,
+            stencil_read_mask: 0,
+            stencil_write_mask: 0,
+        }),
+    };
+    let color_states: &[_] = match kind {
+        Kind::Color => &[color_state_desc],
+        Kind::Depth => &[],
+    };
     let vertex_attrs = vertex_attrs();
     let vertex_buffer_desc = wgpu::VertexBufferDescriptor {
         stride: std::mem::size_of::<Vertex>() as wgpu::BufferAddress,
@@ -287,8 +888,8 @@ fn render_pipeline(
         fragment_stage: Some(fs_desc),
         rasterization_state: Some(raster_desc),
         primitive_topology: wgpu::PrimitiveTopology::TriangleStrip,
-        color_states: &[color_state_desc],
-        depth_stencil_state: None,
+        color_states,
+        depth_stencil_state: depth_stencil_state_desc,
         index_format: wgpu::IndexFormat::Uint16,
         vertex_buffers: &[vertex_buffer_desc],
         sample_count: dst_sample_count,
@@ -0,0 +1,238 @@
+use crate::wgpu;
+use image::RgbaImage;
+
+// The alignment wgpu requires of each row of a buffer used as the destination of a
+// `copy_texture_to_buffer` command.
+const COPY_BYTES_PER_ROW_ALIGNMENT: u32 = 256;
+
+// The number of bytes per pixel for the `Rgba8` family of formats this module supports reading
+// back to an `RgbaImage`.
+const BYTES_PER_PIXEL: u32 = 4;
+
+// Whether `format` is one `TextureTarget::snapshot`/`Snapshot::read` can read back into an
+// `RgbaImage` - i.e. one of the 8-bit-per-channel RGBA formats `BYTES_PER_PIXEL` assumes.
+fn is_supported_format(format: wgpu::TextureFormat) -> bool {
+    matches!(
+        format,
+        wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb
+    )
+}
+
+/// An offscreen color render target: an owned `wgpu::Texture` (and its view), sized, formatted
+/// and multisampled independently of any window or swap chain.
+///
+/// Render into a `TextureTarget` the same way as a swap chain frame, by passing
+/// `texture_view()` as a `RenderPassColorAttachmentDescriptor`'s `attachment`. This allows
+/// rendering frames larger than the window, saving sequences to disk, and running sketches
+/// headless with no visible surface at all.
+///
+/// Call `snapshot` after encoding the render pass to begin an asynchronous read-back of the
+/// rendered pixels as an `RgbaImage`.
+#[derive(Debug)]
+pub struct TextureTarget {
+    texture: wgpu::Texture,
+    texture_view: wgpu::TextureView,
+    size: [u32; 2],
+    format: wgpu::TextureFormat,
+    sample_count: u32,
+}
+
+/// A builder for configuring and constructing a `TextureTarget`.
+#[derive(Clone, Debug)]
+pub struct Builder {
+    size: [u32; 2],
+    format: wgpu::TextureFormat,
+    sample_count: u32,
+}
+
+impl Builder {
+    /// Begin building a `TextureTarget` of the given `size` (in pixels), defaulting to a
+    /// single-sampled `Rgba8UnormSrgb` texture.
+    pub fn new(size: [u32; 2]) -> Self {
+        Builder {
+            size,
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            sample_count: 1,
+        }
+    }
+
+    /// The texture format to render into.
+    pub fn format(mut self, format: wgpu::TextureFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// The number of samples per pixel, for an MSAA target.
+    ///
+    /// A multisampled target must be resolved (e.g. via `wgpu::texture::Reshaper`) to a
+    /// single-sampled texture before its pixels can be read back with `TextureTarget::snapshot`.
+    pub fn sample_count(mut self, sample_count: u32) -> Self {
+        self.sample_count = sample_count;
+        self
+    }
+
+    /// Build the `TextureTarget` with the settings specified on this `Builder`.
+    ///
+    /// Panics if `format` is not one of the 8-bit-per-channel RGBA formats that
+    /// `TextureTarget::snapshot`/`Snapshot::read` know how to read back into an `RgbaImage`.
+    pub fn build(self, device: &wgpu::Device) -> TextureTarget {
+        let Builder {
+            size,
+            format,
+            sample_count,
+        } = self;
+        assert!(
+            is_supported_format(format),
+            "`TextureTarget` only supports reading back `Rgba8Unorm`/`Rgba8UnormSrgb` formats, \
+             found {:?} - build the texture directly via `wgpu::Device::create_texture` instead \
+             if you don't need `TextureTarget::snapshot`",
+            format,
+        );
+        let [width, height] = size;
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth: 1,
+            },
+            array_layer_count: 1,
+            mip_level_count: 1,
+            sample_count,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT
+                | wgpu::TextureUsage::SAMPLED
+                | wgpu::TextureUsage::COPY_SRC,
+        });
+        let texture_view = texture.create_default_view();
+        TextureTarget {
+            texture,
+            texture_view,
+            size,
+            format,
+            sample_count,
+        }
+    }
+}
+
+impl TextureTarget {
+    /// Begin building a `TextureTarget` of the given `size` (in pixels).
+    pub fn builder(size: [u32; 2]) -> Builder {
+        Builder::new(size)
+    }
+
+    /// The texture's dimensions, in pixels.
+    pub fn size(&self) -> [u32; 2] {
+        self.size
+    }
+
+    /// The format the texture was created with.
+    pub fn format(&self) -> wgpu::TextureFormat {
+        self.format
+    }
+
+    /// The number of samples per pixel the texture was created with.
+    pub fn sample_count(&self) -> u32 {
+        self.sample_count
+    }
+
+    /// The texture itself, e.g. to resolve a multisampled intermediary render into it via
+    /// `wgpu::texture::Reshaper`.
+    pub fn texture(&self) -> &wgpu::Texture {
+        &self.texture
+    }
+
+    /// A view onto the texture, ready to be used as a `RenderPassColorAttachmentDescriptor`'s
+    /// `attachment`.
+    pub fn texture_view(&self) -> &wgpu::TextureView {
+        &self.texture_view
+    }
+
+    /// Encode a copy of the texture into a freshly allocated, mappable buffer, padding each row
+    /// out to the 256-byte alignment wgpu requires of buffer-texture copies.
+    ///
+    /// The returned `Snapshot` must not be read until `encoder`'s commands have been submitted to
+    /// the `device`'s queue.
+    pub fn snapshot(&self, device: &wgpu::Device, encoder: &mut wgpu::CommandEncoder) -> Snapshot {
+        let [width, height] = self.size;
+        let unpadded_bytes_per_row = width * BYTES_PER_PIXEL;
+        let padding =
+            (COPY_BYTES_PER_ROW_ALIGNMENT - unpadded_bytes_per_row % COPY_BYTES_PER_ROW_ALIGNMENT)
+                % COPY_BYTES_PER_ROW_ALIGNMENT;
+        let padded_bytes_per_row = unpadded_bytes_per_row + padding;
+        let buffer_size = (padded_bytes_per_row * height) as wgpu::BufferAddress;
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            size: buffer_size,
+            usage: wgpu::BufferUsage::COPY_DST | wgpu::BufferUsage::MAP_READ,
+        });
+        let src = wgpu::TextureCopyView {
+            texture: &self.texture,
+            mip_level: 0,
+            array_layer: 0,
+            origin: wgpu::Origin3d::ZERO,
+        };
+        let dst = wgpu::BufferCopyView {
+            buffer: &buffer,
+            offset: 0,
+            bytes_per_row: padded_bytes_per_row,
+            rows_per_image: height,
+        };
+        let extent = wgpu::Extent3d {
+            width,
+            height,
+            depth: 1,
+        };
+        encoder.copy_texture_to_buffer(src, dst, extent);
+        Snapshot {
+            buffer,
+            buffer_size,
+            width,
+            height,
+            padded_bytes_per_row,
+            unpadded_bytes_per_row,
+        }
+    }
+}
+
+/// A pending, asynchronous read-back of a `TextureTarget`'s pixels, produced by
+/// `TextureTarget::snapshot`.
+#[derive(Debug)]
+pub struct Snapshot {
+    buffer: wgpu::Buffer,
+    buffer_size: wgpu::BufferAddress,
+    width: u32,
+    height: u32,
+    padded_bytes_per_row: u32,
+    unpadded_bytes_per_row: u32,
+}
+
+impl Snapshot {
+    /// Map the snapshot's buffer and copy it into an `RgbaImage`, stripping the per-row padding
+    /// that was inserted to satisfy wgpu's 256-byte buffer-texture copy alignment.
+    ///
+    /// Must only be called after the commands recorded by `TextureTarget::snapshot` have been
+    /// submitted to `device`'s queue; `device.poll(wgpu::Maintain::Wait)` drives the mapping to
+    /// completion so the returned future resolves promptly rather than hanging indefinitely.
+    pub async fn read(self, device: &wgpu::Device) -> RgbaImage {
+        let Snapshot {
+            buffer,
+            buffer_size,
+            width,
+            height,
+            padded_bytes_per_row,
+            unpadded_bytes_per_row,
+        } = self;
+        let mapping = buffer.map_read(0, buffer_size);
+        device.poll(wgpu::Maintain::Wait);
+        let mapped = mapping
+            .await
+            .expect("failed to map texture read-back buffer");
+        let padded = mapped.as_slice();
+        let mut pixels = Vec::with_capacity((unpadded_bytes_per_row * height) as usize);
+        for row in padded.chunks(padded_bytes_per_row as usize) {
+            pixels.extend_from_slice(&row[..unpadded_bytes_per_row as usize]);
+        }
+        RgbaImage::from_raw(width, height, pixels)
+            .expect("read-back pixel buffer did not match the expected image dimensions")
+    }
+}